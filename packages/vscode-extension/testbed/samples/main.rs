@@ -2,21 +2,291 @@
 //!
 //! Lock-free concurrent queue with backoff strategy.
 
-use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicPtr, AtomicU8, AtomicUsize, Ordering};
 use std::ptr;
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-/// Node in the lock-free queue
+/// Epoch-based memory reclamation.
+///
+/// Retiring a node and freeing it are split into two steps so that a node
+/// is never dropped while another thread might still hold a raw reference
+/// to it (loaded from `head`/`next` before a competing CAS). Threads
+/// "pin" themselves for the duration of a `push`/`pop`, publishing the
+/// global epoch they observed; a node retired at epoch `e` is only freed
+/// once every pinned thread has been seen at epoch `e + 2`, at which
+/// point nothing can still be dereferencing it.
+mod epoch {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex, OnceLock, Weak};
+
+    const UNPINNED: usize = usize::MAX;
+
+    /// A thread's last-observed epoch, or `UNPINNED` while not in a
+    /// critical section.
+    struct LocalEpoch {
+        epoch: AtomicUsize,
+    }
+
+    struct Registry {
+        threads: Mutex<Vec<Weak<LocalEpoch>>>,
+    }
+
+    static GLOBAL_EPOCH: AtomicUsize = AtomicUsize::new(0);
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+    fn registry() -> &'static Registry {
+        REGISTRY.get_or_init(|| Registry {
+            threads: Mutex::new(Vec::new()),
+        })
+    }
+
+    thread_local! {
+        static LOCAL: Arc<LocalEpoch> = {
+            let local = Arc::new(LocalEpoch { epoch: AtomicUsize::new(UNPINNED) });
+            registry().threads.lock().unwrap().push(Arc::downgrade(&local));
+            local
+        };
+    }
+
+    /// RAII critical-section handle. While a `Guard` is alive the owning
+    /// thread has published the epoch it pinned at, so reclamation will
+    /// not free anything retired at or after that epoch.
+    pub struct Guard {
+        local: Arc<LocalEpoch>,
+    }
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.local.epoch.store(UNPINNED, Ordering::Release);
+        }
+    }
+
+    /// Enter a critical section. Must be held across any access to a
+    /// node that could concurrently be retired.
+    pub fn pin() -> Guard {
+        LOCAL.with(|local| {
+            let current = GLOBAL_EPOCH.load(Ordering::Relaxed);
+            local.epoch.store(current, Ordering::SeqCst);
+            Guard {
+                local: Arc::clone(local),
+            }
+        })
+    }
+
+    pub(crate) fn current_epoch() -> usize {
+        GLOBAL_EPOCH.load(Ordering::Relaxed)
+    }
+
+    /// Advance the global epoch if no pinned thread is lagging behind it.
+    /// Returns the new epoch on success.
+    pub(crate) fn try_advance() -> Option<usize> {
+        let global = GLOBAL_EPOCH.load(Ordering::SeqCst);
+        let mut threads = registry().threads.lock().unwrap();
+        // Prune slots for threads that have since exited while we're
+        // already holding the lock and scanning every entry - otherwise
+        // this vector only ever grows, and every `pin()`/`try_advance()`
+        // gets linearly slower over the life of a long-running process
+        // with thread churn (e.g. anything spawning per-call threads for
+        // `pop_blocking`/`Selector`).
+        let mut lagging = false;
+        threads.retain(|weak| {
+            let Some(local) = weak.upgrade() else {
+                return false;
+            };
+            let pinned = local.epoch.load(Ordering::SeqCst);
+            if pinned != UNPINNED && pinned != global {
+                // Still observing an older epoch; not safe to advance yet.
+                lagging = true;
+            }
+            true
+        });
+        if lagging {
+            return None;
+        }
+        drop(threads);
+        let new_epoch = global.wrapping_add(1);
+        GLOBAL_EPOCH
+            .compare_exchange(global, new_epoch, Ordering::SeqCst, Ordering::Relaxed)
+            .ok()
+            .map(|_| new_epoch)
+    }
+}
+
+/// A registry of parked threads waiting on a queue, paired with a
+/// parker-based wakeup so consumers (or, for bounded queues, blocked
+/// producers) don't have to busy-poll.
+mod waiter {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread::{self, Thread};
+    use std::time::Instant;
+
+    /// A single parked thread's wakeup flag. The flag is what actually
+    /// makes wakeups safe: a `notify` delivered before the thread parks
+    /// is recorded here instead of lost, so the parked thread's first
+    /// check of the flag (before it even calls `park`) already sees it.
+    pub struct Waiter {
+        thread: Thread,
+        notified: AtomicBool,
+    }
+
+    impl Waiter {
+        /// Creates a standalone waiter for the current thread. Most
+        /// callers want `WaiterList::register`; this is exposed so a
+        /// selector can share one waiter across several queues' lists.
+        pub(crate) fn new() -> Arc<Self> {
+            Arc::new(Waiter {
+                thread: thread::current(),
+                notified: AtomicBool::new(false),
+            })
+        }
+
+        pub fn is_notified(&self) -> bool {
+            self.notified.load(Ordering::Acquire)
+        }
+
+        /// Wake this waiter if it hasn't already been notified.
+        pub fn notify(&self) {
+            if !self.notified.swap(true, Ordering::AcqRel) {
+                self.thread.unpark();
+            }
+        }
+    }
+
+    /// Parks the current thread until `waiter` is notified, or (if
+    /// `deadline` is `Some`) until the deadline elapses. Returns `false`
+    /// on timeout.
+    pub fn park_until_notified(waiter: &Waiter, deadline: Option<Instant>) -> bool {
+        loop {
+            if waiter.is_notified() {
+                return true;
+            }
+            match deadline {
+                None => thread::park(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return false;
+                    }
+                    thread::park_timeout(deadline - now);
+                }
+            }
+        }
+    }
+
+    /// FIFO registry of threads parked on a single queue.
+    #[derive(Default)]
+    pub struct WaiterList {
+        waiters: Mutex<Vec<Arc<Waiter>>>,
+    }
+
+    impl WaiterList {
+        pub fn new() -> Self {
+            WaiterList {
+                waiters: Mutex::new(Vec::new()),
+            }
+        }
+
+        /// Register the current thread, returning a handle to park on.
+        pub fn register(&self) -> Arc<Waiter> {
+            let waiter = Waiter::new();
+            self.register_existing(Arc::clone(&waiter));
+            waiter
+        }
+
+        /// Register an already-created waiter, e.g. one shared across
+        /// several queues by a `Selector`.
+        pub(crate) fn register_existing(&self, waiter: Arc<Waiter>) {
+            self.waiters.lock().unwrap().push(waiter);
+        }
+
+        /// Remove `waiter` from the list. No-op if already removed (e.g.
+        /// by a concurrent `notify_one`).
+        pub fn deregister(&self, waiter: &Arc<Waiter>) {
+            let mut waiters = self.waiters.lock().unwrap();
+            if let Some(pos) = waiters.iter().position(|w| Arc::ptr_eq(w, waiter)) {
+                waiters.remove(pos);
+            }
+        }
+
+        /// Wake the oldest registered waiter, if any.
+        pub fn notify_one(&self) {
+            let waiter = {
+                let mut waiters = self.waiters.lock().unwrap();
+                if waiters.is_empty() {
+                    None
+                } else {
+                    Some(waiters.remove(0))
+                }
+            };
+            if let Some(waiter) = waiter {
+                waiter.notify();
+            }
+        }
+    }
+}
+
+/// A reservation left behind by `pop_blocking`/`pop_timeout` when it finds
+/// the queue empty: a slot a future `push` can deliver straight into, plus
+/// the waiter to unpark once that happens. `state` arbitrates the handoff
+/// so a `push` and a timing-out `pop_timeout` can never both believe they
+/// won - exactly one of "fulfill" and "cancel" succeeds.
+struct Reservation<T> {
+    slot: UnsafeCell<Option<T>>,
+    waiter: Arc<waiter::Waiter>,
+    state: AtomicU8,
+}
+
+const RESERVATION_PENDING: u8 = 0;
+const RESERVATION_FULFILLED: u8 = 1;
+const RESERVATION_CANCELLED: u8 = 2;
+
+impl<T> Reservation<T> {
+    fn new() -> Self {
+        Reservation {
+            slot: UnsafeCell::new(None),
+            waiter: waiter::Waiter::new(),
+            state: AtomicU8::new(RESERVATION_PENDING),
+        }
+    }
+}
+
+// The slot is only ever written by the single `push` that wins the
+// `state` handoff, and only ever read by the single `pop_blocking`/
+// `pop_timeout` call that owns this reservation, so `Sync` is sound
+// despite the bare `UnsafeCell`.
+unsafe impl<T: Send> Send for Reservation<T> {}
+unsafe impl<T: Send> Sync for Reservation<T> {}
+
+/// A non-sentinel node is either data waiting to be popped, or a
+/// reservation left by a consumer that found the queue empty. The queue
+/// never links a `Data` node next to a `Blocked` one - see the module doc
+/// on `Node` for why.
+enum Entry<T> {
+    Data(T),
+    Blocked(Arc<Reservation<T>>),
+}
+
+/// Node in the lock-free queue.
+///
+/// In dual-queue mode, `entry` is `None` only for the sentinel; every real
+/// node is uniformly `Data` or uniformly `Blocked` depending on whether
+/// producers or consumers are currently ahead. `push` turns a `Blocked`
+/// head back into an empty-of-reservations queue by fulfilling it in
+/// place instead of ever appending `Data` next to a `Blocked` node.
 struct Node<T> {
-    value: Option<T>,
+    entry: Option<Entry<T>>,
     next: AtomicPtr<Node<T>>,
 }
 
 impl<T> Node<T> {
-    fn new(value: Option<T>) -> *mut Self {
+    fn new(entry: Option<Entry<T>>) -> *mut Self {
         Box::into_raw(Box::new(Node {
-            value,
+            entry,
             next: AtomicPtr::new(ptr::null_mut()),
         }))
     }
@@ -52,41 +322,228 @@ impl Backoff {
     }
 }
 
+/// Retired nodes awaiting reclamation, bucketed by the epoch they were
+/// retired in. Three rotating bags are enough: once the global epoch has
+/// advanced twice past a bag's epoch, nothing can still observe it.
+struct Garbage<T> {
+    bags: Mutex<[Vec<*mut Node<T>>; 3]>,
+    retire_count: AtomicUsize,
+}
+
+impl<T> Garbage<T> {
+    fn new() -> Self {
+        Garbage {
+            bags: Mutex::new([Vec::new(), Vec::new(), Vec::new()]),
+            retire_count: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// Scan for reclaimable garbage after every `RECLAIM_EVERY` retirements,
+/// rather than on every single one.
+const RECLAIM_EVERY: usize = 32;
+
+/// Pads `T` out to a 128-byte cache line so that two adjacent instances
+/// never share one. Matches the layout crossbeam's queues use: on most
+/// x86 cores the prefetcher pulls in pairs of 64-byte lines together, so
+/// padding to a single 64-byte line isn't always enough to stop a hot
+/// neighbor's writes from bouncing the cache line between cores.
+#[repr(align(128))]
+struct CachePadded<T> {
+    value: T,
+}
+
+impl<T> CachePadded<T> {
+    fn new(value: T) -> Self {
+        CachePadded { value }
+    }
+}
+
+impl<T> std::ops::Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> std::ops::DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
 // !! This queue uses unsafe code - review carefully before modifying
 pub struct LockFreeQueue<T> {
-    head: AtomicPtr<Node<T>>,
-    tail: AtomicPtr<Node<T>>,
-    len: AtomicUsize,
+    // `head` and `tail` are padded apart so consumer CAS traffic on one
+    // doesn't ping-pong the cache line producers are hammering on the
+    // other - see `CachePadded`.
+    head: CachePadded<AtomicPtr<Node<T>>>,
+    tail: CachePadded<AtomicPtr<Node<T>>>,
+    #[cfg(feature = "exact-len")]
+    len: CachePadded<AtomicUsize>,
+    garbage: Garbage<T>,
+    waiters: waiter::WaiterList,
 }
 
 impl<T> LockFreeQueue<T> {
     pub fn new() -> Self {
         let sentinel = Node::sentinel();
         LockFreeQueue {
-            head: AtomicPtr::new(sentinel),
-            tail: AtomicPtr::new(sentinel),
-            len: AtomicUsize::new(0),
+            head: CachePadded::new(AtomicPtr::new(sentinel)),
+            tail: CachePadded::new(AtomicPtr::new(sentinel)),
+            #[cfg(feature = "exact-len")]
+            len: CachePadded::new(AtomicUsize::new(0)),
+            garbage: Garbage::new(),
+            waiters: waiter::WaiterList::new(),
+        }
+    }
+
+    /// Defer freeing `ptr` until no thread can still be observing it.
+    fn retire(&self, ptr: *mut Node<T>) {
+        let retired_epoch = epoch::current_epoch();
+        {
+            let mut bags = self.garbage.bags.lock().unwrap();
+            bags[retired_epoch % 3].push(ptr);
+        }
+        let count = self.garbage.retire_count.fetch_add(1, Ordering::Relaxed) + 1;
+        if count.is_multiple_of(RECLAIM_EVERY) {
+            self.collect();
+        }
+    }
+
+    /// Try to advance the epoch and free whichever bag just became safe.
+    fn collect(&self) {
+        let Some(new_epoch) = epoch::try_advance() else {
+            return;
+        };
+        // Once the epoch has advanced to `new_epoch`, anything retired at
+        // `new_epoch - 2` (i.e. `new_epoch + 1` modulo 3 rotating bags) is
+        // guaranteed unreachable.
+        let stale = (new_epoch + 1) % 3;
+        let mut freed = Vec::new();
+        {
+            let mut bags = self.garbage.bags.lock().unwrap();
+            std::mem::swap(&mut freed, &mut bags[stale]);
+        }
+        for ptr in freed {
+            unsafe { drop(Box::from_raw(ptr)) };
         }
     }
 
+    /// Returns the number of `Data` items currently buffered in the
+    /// queue.
+    ///
+    /// Only compiled in with the `exact-len` feature: the unconditional
+    /// `fetch_add`/`fetch_sub` an exact count requires on every
+    /// `push`/`pop` is itself a contention point, on top of the
+    /// `head`/`tail` CAS traffic, and most callers only need
+    /// [`is_empty`](Self::is_empty), which stays available either way.
+    /// The counter uses `Relaxed` ordering: it reflects every `push`/`pop`
+    /// that has completed by the time it's read, but a concurrent caller
+    /// may already see a stale value by the time they act on it.
+    #[cfg(feature = "exact-len")]
     pub fn len(&self) -> usize {
         self.len.load(Ordering::Relaxed)
     }
 
+    /// Reports whether a `pop()` would currently find no data - i.e.
+    /// whether the queue holds only `Blocked` reservations, or nothing at
+    /// all. Unlike `len`, this is always available and doesn't require
+    /// the exact counter: it's a direct, lock-free peek at the head.
     pub fn is_empty(&self) -> bool {
-        self.len() == 0
+        let _guard = epoch::pin();
+        let head = self.head.load(Ordering::Acquire);
+        let head_ref = unsafe { &*head };
+        let next = head_ref.next.load(Ordering::Acquire);
+        if next.is_null() {
+            return true;
+        }
+        let next_ref = unsafe { &*next };
+        !matches!(next_ref.entry, Some(Entry::Data(_)))
     }
 
     pub fn push(&self, value: T) {
-        let new_node = Node::new(Some(value));
+        let mut pending = Some(value);
+        let mut data_node: Option<*mut Node<T>> = None;
         let mut backoff = Backoff::new();
+        let _guard = epoch::pin();
 
         loop {
+            // Before committing to an ordinary `Data` append, see whether
+            // a consumer already left a `Blocked` reservation at the head
+            // - if so, hand the value straight to it instead of growing
+            // the list. Once we've allocated our own `Data` node below we
+            // no longer look here; we're committed to appending it.
+            if data_node.is_none() {
+                let head = self.head.load(Ordering::Acquire);
+                let tail = self.tail.load(Ordering::Acquire);
+                let head_ref = unsafe { &*head };
+                let next = head_ref.next.load(Ordering::Acquire);
+
+                if head != tail && !next.is_null() {
+                    let next_ref = unsafe { &*next };
+                    if let Some(Entry::Blocked(reservation)) = next_ref.entry.as_ref() {
+                        let reservation = Arc::clone(reservation);
+                        if self
+                            .head
+                            .compare_exchange(head, next, Ordering::Release, Ordering::Relaxed)
+                            .is_ok()
+                        {
+                            self.retire(head);
+                            if reservation
+                                .state
+                                .compare_exchange(
+                                    RESERVATION_PENDING,
+                                    RESERVATION_FULFILLED,
+                                    Ordering::AcqRel,
+                                    Ordering::Acquire,
+                                )
+                                .is_ok()
+                            {
+                                unsafe { *reservation.slot.get() = Some(pending.take().unwrap()) };
+                                reservation.waiter.notify();
+                                return;
+                            }
+                            // The waiting consumer already timed out and
+                            // cancelled this reservation; `pending` is
+                            // untouched, so loop back and try the next
+                            // node (another reservation, or room to
+                            // append `Data`).
+                            continue;
+                        }
+                        backoff.spin();
+                        continue;
+                    }
+                }
+            }
+
+            let new_node = *data_node
+                .get_or_insert_with(|| Node::new(Some(Entry::Data(pending.take().unwrap()))));
+
             let tail = self.tail.load(Ordering::Acquire);
             let tail_ref = unsafe { &*tail };
             let next = tail_ref.next.load(Ordering::Acquire);
 
             if next.is_null() {
+                if let Some(Entry::Blocked(reservation)) = tail_ref.entry.as_ref() {
+                    if reservation.state.load(Ordering::Acquire) == RESERVATION_PENDING {
+                        // A reservation landed at `tail` between our
+                        // head-side check and here (the queue looked empty
+                        // a moment ago) and is still waiting. Appending
+                        // `Data` after it would mix kinds in the same
+                        // chain segment and `pop` would never see this
+                        // value - loop back so the head-side fulfillment
+                        // path above gets a chance to hand it off directly
+                        // instead.
+                        backoff.spin();
+                        continue;
+                    }
+                    // This reservation has already been fulfilled or
+                    // cancelled - it no longer carries a pending consumer
+                    // and is just a resolved sentinel at this point, so
+                    // it's safe to append ordinary `Data` behind it.
+                }
                 if tail_ref
                     .next
                     .compare_exchange(
@@ -103,7 +560,9 @@ impl<T> LockFreeQueue<T> {
                         Ordering::Release,
                         Ordering::Relaxed,
                     );
+                    #[cfg(feature = "exact-len")]
                     self.len.fetch_add(1, Ordering::Relaxed);
+                    self.waiters.notify_one();
                     return;
                 }
             } else {
@@ -118,9 +577,9 @@ impl<T> LockFreeQueue<T> {
         }
     }
 
-    // ?? Should we add a try_pop with timeout?
     pub fn pop(&self) -> Option<T> {
         let mut backoff = Backoff::new();
+        let _guard = epoch::pin();
 
         loop {
             let head = self.head.load(Ordering::Acquire);
@@ -140,13 +599,40 @@ impl<T> LockFreeQueue<T> {
                 );
             } else if !next.is_null() {
                 let next_ref = unsafe { &*next };
+                if let Some(Entry::Blocked(reservation)) = next_ref.entry.as_ref() {
+                    if reservation.state.load(Ordering::Acquire) == RESERVATION_PENDING {
+                        // Still pending: nothing for us to take yet, and
+                        // nothing further down the chain is reachable
+                        // until this one resolves.
+                        return None;
+                    }
+                    // This reservation has already been fulfilled (its
+                    // value went straight to the waiting consumer) or
+                    // cancelled (timed out) - either way nobody will ever
+                    // read it again, so dequeue it and keep looking rather
+                    // than reporting `None` while data may sit further
+                    // down the chain.
+                    if self
+                        .head
+                        .compare_exchange(head, next, Ordering::Release, Ordering::Relaxed)
+                        .is_ok()
+                    {
+                        self.retire(head);
+                    }
+                    backoff.spin();
+                    continue;
+                }
                 if self
                     .head
                     .compare_exchange(head, next, Ordering::Release, Ordering::Relaxed)
                     .is_ok()
                 {
-                    let value = unsafe { ptr::read(&next_ref.value) };
-                    unsafe { drop(Box::from_raw(head)) };
+                    let value = match unsafe { ptr::read(&next_ref.entry) } {
+                        Some(Entry::Data(value)) => Some(value),
+                        _ => unreachable!("head.next must be Data once Blocked is ruled out"),
+                    };
+                    self.retire(head);
+                    #[cfg(feature = "exact-len")]
                     self.len.fetch_sub(1, Ordering::Relaxed);
                     return value;
                 }
@@ -154,13 +640,152 @@ impl<T> LockFreeQueue<T> {
             backoff.spin();
         }
     }
+
+    /// Appends a `Blocked` reservation node carrying `reservation`, unless
+    /// the tail already has a `Data` node linked in, in which case the
+    /// caller should just `pop()` that instead of reserving.
+    fn enqueue_reservation(&self, reservation: Arc<Reservation<T>>) -> bool {
+        let new_node = Node::new(Some(Entry::Blocked(reservation)));
+        let mut backoff = Backoff::new();
+        let _guard = epoch::pin();
+
+        loop {
+            let tail = self.tail.load(Ordering::Acquire);
+            let tail_ref = unsafe { &*tail };
+            let next = tail_ref.next.load(Ordering::Acquire);
+
+            if next.is_null() {
+                if matches!(tail_ref.entry, Some(Entry::Data(_))) {
+                    // A value landed at `tail` between our caller's
+                    // empty-queue check and here - the caller should pop
+                    // that instead of us reserving behind it.
+                    unsafe { drop(Box::from_raw(new_node)) };
+                    return false;
+                }
+                if tail_ref
+                    .next
+                    .compare_exchange(
+                        ptr::null_mut(),
+                        new_node,
+                        Ordering::Release,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+                {
+                    let _ = self.tail.compare_exchange(
+                        tail,
+                        new_node,
+                        Ordering::Release,
+                        Ordering::Relaxed,
+                    );
+                    return true;
+                }
+            } else {
+                let next_ref = unsafe { &*next };
+                if matches!(next_ref.entry, Some(Entry::Data(_))) {
+                    unsafe { drop(Box::from_raw(new_node)) };
+                    return false;
+                }
+                let _ = self.tail.compare_exchange(
+                    tail,
+                    next,
+                    Ordering::Release,
+                    Ordering::Relaxed,
+                );
+            }
+            backoff.spin();
+        }
+    }
+
+    /// Pops a value, parking the calling thread instead of spinning while
+    /// the queue is empty. Rather than registering on the generic waiter
+    /// list and retrying the whole CAS loop on every wakeup, this leaves a
+    /// `Blocked` reservation in the queue itself so the fulfilling `push`
+    /// can hand the value straight to us - no re-contending for `head`.
+    pub fn pop_blocking(&self) -> T {
+        loop {
+            if let Some(value) = self.pop() {
+                return value;
+            }
+            let reservation = Arc::new(Reservation::new());
+            if self.enqueue_reservation(Arc::clone(&reservation)) {
+                waiter::park_until_notified(&reservation.waiter, None);
+                return unsafe { (*reservation.slot.get()).take() }
+                    .expect("a notified reservation always carries a value");
+            }
+            // A `Data` node appeared between our failed `pop` and the
+            // reservation attempt; loop back and just pop it.
+        }
+    }
+
+    /// Pops a value, parking up to `timeout` while the queue is empty.
+    /// Returns `None` if the deadline elapses first.
+    pub fn pop_timeout(&self, timeout: Duration) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(value) = self.pop() {
+                return Some(value);
+            }
+            let reservation = Arc::new(Reservation::new());
+            if !self.enqueue_reservation(Arc::clone(&reservation)) {
+                continue;
+            }
+            if waiter::park_until_notified(&reservation.waiter, Some(deadline)) {
+                return Some(
+                    unsafe { (*reservation.slot.get()).take() }
+                        .expect("a notified reservation always carries a value"),
+                );
+            }
+            // Timed out. Race a `push` that might be fulfilling us right
+            // now via the `state` handoff: whichever of "cancel" and
+            // "fulfill" lands first on the compare-exchange wins.
+            if reservation
+                .state
+                .compare_exchange(
+                    RESERVATION_PENDING,
+                    RESERVATION_CANCELLED,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                )
+                .is_ok()
+            {
+                return None;
+            }
+            // A `push` already claimed `FULFILLED` before we could
+            // cancel; it is in the middle of writing the slot and will
+            // notify us imminently, so wait for that rather than reading
+            // the slot directly (only the notify gives us the happens-
+            // before edge over its write).
+            waiter::park_until_notified(&reservation.waiter, None);
+            return Some(
+                unsafe { (*reservation.slot.get()).take() }
+                    .expect("a fulfilled reservation always carries a value"),
+            );
+        }
+    }
 }
 
 impl<T> Drop for LockFreeQueue<T> {
     fn drop(&mut self) {
-        while self.pop().is_some() {}
-        let head = self.head.load(Ordering::Relaxed);
-        unsafe { drop(Box::from_raw(head)) };
+        // Walk the whole chain directly rather than draining through
+        // `pop()`: with dual-queue mode, trailing nodes may be `Blocked`
+        // reservations left by consumers that never got fulfilled, which
+        // `pop()` (correctly) refuses to take.
+        let mut node = self.head.load(Ordering::Relaxed);
+        while !node.is_null() {
+            let next = unsafe { (*node).next.load(Ordering::Relaxed) };
+            unsafe { drop(Box::from_raw(node)) };
+            node = next;
+        }
+        // `&mut self` means no other thread can be observing retired
+        // nodes at this point, so it's safe to free whatever is left in
+        // the garbage bags without waiting on the epoch to advance.
+        let mut bags = self.garbage.bags.lock().unwrap();
+        for bag in bags.iter_mut() {
+            for ptr in bag.drain(..) {
+                unsafe { drop(Box::from_raw(ptr)) };
+            }
+        }
     }
 }
 
@@ -174,9 +799,422 @@ impl<T> Default for LockFreeQueue<T> {
     }
 }
 
-fn main() {
-    use std::sync::Arc;
+/// Returns a cheaply-obtained, not-cryptographically-random `usize`,
+/// good enough for breaking ties between queues without favoring one.
+fn random_usize() -> usize {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    RandomState::new().build_hasher().finish() as usize
+}
+
+/// Waits on several [`LockFreeQueue`]s at once and pops from whichever
+/// becomes ready first, similar to crossbeam-channel's `select!`.
+pub struct Selector<'a, T> {
+    queues: Vec<&'a LockFreeQueue<T>>,
+}
+
+impl<'a, T> Selector<'a, T> {
+    pub fn new() -> Self {
+        Selector { queues: Vec::new() }
+    }
+
+    /// Adds a queue to the set this selector waits on.
+    pub fn with_queue(mut self, queue: &'a LockFreeQueue<T>) -> Self {
+        self.queues.push(queue);
+        self
+    }
 
+    /// Pops from whichever participating queue is non-empty first,
+    /// returning `(index, value)` identifying which queue yielded it (by
+    /// position in the order queues were added). Blocks while every
+    /// queue is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no queue has been registered via [`Selector::with_queue`]:
+    /// with nothing to wait on, there is nothing that could ever wake the
+    /// calling thread.
+    pub fn select_pop(&self) -> (usize, T) {
+        assert!(
+            !self.queues.is_empty(),
+            "Selector::select_pop: no queues registered, would block forever"
+        );
+
+        loop {
+            // Try each queue once, in a randomized order so no single
+            // queue is starved by always being checked last.
+            for i in self.randomized_order() {
+                if let Some(value) = self.queues[i].pop() {
+                    return (i, value);
+                }
+            }
+
+            let w = waiter::Waiter::new();
+            for queue in &self.queues {
+                queue.waiters.register_existing(Arc::clone(&w));
+            }
+
+            // Re-check after registering: a push landing between our
+            // last failed pop and this registration must not be missed.
+            if self.queues.iter().any(|q| !q.is_empty()) {
+                self.deregister_all(&w);
+                continue;
+            }
+
+            waiter::park_until_notified(&w, None);
+            // Exactly one queue's `notify_one` claims this waiter; the
+            // rest still hold a reference to it until we remove it here.
+            self.deregister_all(&w);
+        }
+    }
+
+    fn randomized_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.queues.len()).collect();
+        for i in (1..order.len()).rev() {
+            let j = random_usize() % (i + 1);
+            order.swap(i, j);
+        }
+        order
+    }
+
+    fn deregister_all(&self, waiter: &Arc<waiter::Waiter>) {
+        for queue in &self.queues {
+            queue.waiters.deregister(waiter);
+        }
+    }
+}
+
+impl<'a, T> Default for Selector<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A slot in an `ArrayQueue`'s ring buffer.
+///
+/// `seq` tracks which "lap" around the buffer the slot is on, following
+/// Dmitry Vyukov's bounded MPMC queue: a producer may write once `seq`
+/// equals its target `tail`, and a consumer may read once `seq` equals
+/// its target `head + 1`.
+struct Slot<T> {
+    value: UnsafeCell<MaybeUninit<T>>,
+    seq: AtomicUsize,
+}
+
+/// A fixed-capacity, allocation-free multi-producer/multi-consumer queue.
+///
+/// Unlike [`LockFreeQueue`], `ArrayQueue` never allocates after
+/// construction and provides backpressure: `push` fails once the queue
+/// is full instead of growing. Capacity is rounded up to the next power
+/// of two so slot indices can be masked instead of taken modulo.
+pub struct ArrayQueue<T> {
+    slots: Box<[Slot<T>]>,
+    mask: usize,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    /// Producers parked on `push_blocking` while the queue is full.
+    waiters: waiter::WaiterList,
+}
+
+impl<T> ArrayQueue<T> {
+    /// Creates a queue that can hold at least `capacity` elements.
+    ///
+    /// The effective capacity is always at least 2: with a single slot,
+    /// the per-slot sequence number can't tell "just written, not yet
+    /// popped" apart from "popped, ready for the next lap", which would
+    /// let a racing `push` overwrite an unconsumed value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "ArrayQueue capacity must be non-zero");
+        let capacity = capacity.max(2).next_power_of_two();
+        let slots = (0..capacity)
+            .map(|i| Slot {
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+                seq: AtomicUsize::new(i),
+            })
+            .collect();
+        ArrayQueue {
+            slots,
+            mask: capacity - 1,
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            waiters: waiter::WaiterList::new(),
+        }
+    }
+
+    /// The fixed capacity of the queue (a power of two).
+    pub fn capacity(&self) -> usize {
+        self.mask + 1
+    }
+
+    /// Attempts to push `value` onto the queue, returning it back if the
+    /// queue is full.
+    pub fn push(&self, value: T) -> Result<(), T> {
+        let mut backoff = Backoff::new();
+        let mut tail = self.tail.load(Ordering::Relaxed);
+
+        loop {
+            let slot = &self.slots[tail & self.mask];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let diff = seq as isize - tail as isize;
+
+            if diff == 0 {
+                if self
+                    .tail
+                    .compare_exchange_weak(
+                        tail,
+                        tail + 1,
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+                {
+                    unsafe { (*slot.value.get()).write(value) };
+                    slot.seq.store(tail + 1, Ordering::Release);
+                    return Ok(());
+                }
+                backoff.spin();
+                tail = self.tail.load(Ordering::Relaxed);
+            } else if diff < 0 {
+                return Err(value);
+            } else {
+                backoff.spin();
+                tail = self.tail.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Pushes `value`, parking the calling thread instead of spinning
+    /// while the queue is full.
+    pub fn push_blocking(&self, mut value: T) {
+        loop {
+            match self.push(value) {
+                Ok(()) => return,
+                Err(v) => value = v,
+            }
+            let w = self.waiters.register();
+            // Re-check for room after registering so a `pop` that freed
+            // a slot between our failed `push` and this registration
+            // can't be missed (lost wakeup).
+            match self.push(value) {
+                Ok(()) => {
+                    self.waiters.deregister(&w);
+                    return;
+                }
+                Err(v) => value = v,
+            }
+            waiter::park_until_notified(&w, None);
+        }
+    }
+
+    /// Attempts to pop a value from the queue, returning `None` if it is
+    /// currently empty.
+    pub fn pop(&self) -> Option<T> {
+        let mut backoff = Backoff::new();
+        let mut head = self.head.load(Ordering::Relaxed);
+
+        loop {
+            let slot = &self.slots[head & self.mask];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let diff = seq as isize - (head + 1) as isize;
+
+            if diff == 0 {
+                if self
+                    .head
+                    .compare_exchange_weak(
+                        head,
+                        head + 1,
+                        Ordering::Relaxed,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+                {
+                    let value = unsafe { (*slot.value.get()).assume_init_read() };
+                    slot.seq.store(head + self.capacity(), Ordering::Release);
+                    self.waiters.notify_one();
+                    return Some(value);
+                }
+                backoff.spin();
+                head = self.head.load(Ordering::Relaxed);
+            } else if diff < 0 {
+                return None;
+            } else {
+                backoff.spin();
+                head = self.head.load(Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl<T> Drop for ArrayQueue<T> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+unsafe impl<T: Send> Send for ArrayQueue<T> {}
+unsafe impl<T: Send> Sync for ArrayQueue<T> {}
+
+/// Node used by the single-producer/single-consumer fast path. Kept
+/// separate from [`Node`] since `SpscQueue` recycles nodes rather than
+/// freeing them, and has no use for epoch-based reclamation: with only
+/// one producer and one consumer there's no other thread that could
+/// still be observing a node once it's unlinked.
+struct SpscNode<T> {
+    value: Option<T>,
+    next: AtomicPtr<SpscNode<T>>,
+}
+
+impl<T> SpscNode<T> {
+    fn new(value: Option<T>) -> *mut Self {
+        Box::into_raw(Box::new(SpscNode {
+            value,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }))
+    }
+}
+
+/// State shared between a queue's `Producer` and `Consumer` halves.
+///
+/// `head`/`tail` need no atomics at all: `head` is only ever touched by
+/// the consumer and `tail` only by the producer. The single cross-thread
+/// synchronization point is each node's `next` pointer, published with
+/// `Release` by the producer and observed with `Acquire` by the
+/// consumer.
+struct SpscShared<T> {
+    head: UnsafeCell<*mut SpscNode<T>>,
+    tail: UnsafeCell<*mut SpscNode<T>>,
+    /// A single recycled node. Bounding the cache at one node is enough
+    /// to eliminate allocation churn for a producer/consumer pair
+    /// running at matched rates, without letting the cache grow
+    /// unboundedly if the consumer falls behind.
+    free_slot: AtomicPtr<SpscNode<T>>,
+}
+
+unsafe impl<T: Send> Send for SpscShared<T> {}
+unsafe impl<T: Send> Sync for SpscShared<T> {}
+
+impl<T> SpscShared<T> {
+    /// Reclaims `node` into the cache, resetting its value and next
+    /// pointer first. If the cache already holds a node, the evicted one
+    /// is freed rather than letting the cache grow.
+    fn recycle(&self, node: *mut SpscNode<T>) {
+        unsafe {
+            (*node).value = None;
+            (*node).next.store(ptr::null_mut(), Ordering::Relaxed);
+        }
+        let evicted = self.free_slot.swap(node, Ordering::AcqRel);
+        if !evicted.is_null() {
+            unsafe { drop(Box::from_raw(evicted)) };
+        }
+    }
+
+    /// Takes the cached node if one is available, otherwise allocates.
+    fn node_for(&self, value: T) -> *mut SpscNode<T> {
+        let cached = self.free_slot.swap(ptr::null_mut(), Ordering::AcqRel);
+        if cached.is_null() {
+            SpscNode::new(Some(value))
+        } else {
+            unsafe { (*cached).value = Some(value) };
+            cached
+        }
+    }
+}
+
+impl<T> Drop for SpscShared<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let mut node = *self.head.get();
+            while !node.is_null() {
+                let next = (*node).next.load(Ordering::Relaxed);
+                drop(Box::from_raw(node));
+                node = next;
+            }
+            let cached = self.free_slot.swap(ptr::null_mut(), Ordering::Relaxed);
+            if !cached.is_null() {
+                drop(Box::from_raw(cached));
+            }
+        }
+    }
+}
+
+/// The producing half of an [`SpscQueue`]. `Send` but intentionally not
+/// `Clone`, so the one-pusher invariant the hot path relies on is
+/// enforced at the type level rather than by convention.
+pub struct Producer<T> {
+    shared: Arc<SpscShared<T>>,
+}
+
+unsafe impl<T: Send> Send for Producer<T> {}
+
+impl<T> Producer<T> {
+    pub fn push(&self, value: T) {
+        let new_node = self.shared.node_for(value);
+        unsafe {
+            let tail = *self.shared.tail.get();
+            (*tail).next.store(new_node, Ordering::Release);
+            *self.shared.tail.get() = new_node;
+        }
+    }
+}
+
+/// The consuming half of an [`SpscQueue`]. `Send` but intentionally not
+/// `Clone`, so the one-popper invariant the hot path relies on is
+/// enforced at the type level rather than by convention.
+pub struct Consumer<T> {
+    shared: Arc<SpscShared<T>>,
+}
+
+unsafe impl<T: Send> Send for Consumer<T> {}
+
+impl<T> Consumer<T> {
+    pub fn pop(&self) -> Option<T> {
+        unsafe {
+            let head = *self.shared.head.get();
+            let next = (*head).next.load(Ordering::Acquire);
+            if next.is_null() {
+                return None;
+            }
+            let value = (*next).value.take();
+            *self.shared.head.get() = next;
+            self.shared.recycle(head);
+            value
+        }
+    }
+}
+
+/// A single-producer/single-consumer queue, for the common case where
+/// the full `LockFreeQueue` MPMC machinery (CAS loops, epoch
+/// reclamation) is more than the workload needs. There's no standalone
+/// `SpscQueue` value to push or pop through; [`SpscQueue::split`] hands
+/// out the `Producer`/`Consumer` ends that statically enforce the
+/// one-pusher/one-popper invariant this queue's lock-freedom depends on.
+pub struct SpscQueue<T> {
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T> SpscQueue<T> {
+    /// Creates a queue and splits it into its producer and consumer ends.
+    pub fn split() -> (Producer<T>, Consumer<T>) {
+        let sentinel = SpscNode::new(None);
+        let shared = Arc::new(SpscShared {
+            head: UnsafeCell::new(sentinel),
+            tail: UnsafeCell::new(sentinel),
+            free_slot: AtomicPtr::new(ptr::null_mut()),
+        });
+        (
+            Producer {
+                shared: Arc::clone(&shared),
+            },
+            Consumer { shared },
+        )
+    }
+}
+
+fn main() {
     let queue = Arc::new(LockFreeQueue::new());
     let mut handles = vec![];
 
@@ -190,22 +1228,14 @@ fn main() {
         }));
     }
 
-    // Spawn consumer threads
+    // Spawn consumer threads. `pop_blocking` parks each consumer while the
+    // queue is empty instead of busy-polling with a sleep.
     for _ in 0..2 {
         let q = Arc::clone(&queue);
         handles.push(thread::spawn(move || {
-            let mut count = 0;
-            loop {
-                if q.pop().is_some() {
-                    count += 1;
-                } else {
-                    thread::sleep(Duration::from_micros(100));
-                }
-                if count >= 2000 {
-                    break;
-                }
+            for _ in 0..2000 {
+                q.pop_blocking();
             }
-            count
         }));
     }
 
@@ -213,7 +1243,7 @@ fn main() {
         let _ = handle.join();
     }
 
-    println!("Final queue length: {}", queue.len());
+    println!("Queue drained: {}", queue.is_empty());
 }
 
 #[cfg(test)]
@@ -239,4 +1269,274 @@ mod tests {
         assert!(queue.is_empty());
         assert_eq!(queue.pop(), None);
     }
+
+    #[test]
+    fn test_array_queue_push_pop() {
+        let queue = ArrayQueue::new(4);
+        queue.push(1).unwrap();
+        queue.push(2).unwrap();
+        queue.push(3).unwrap();
+
+        assert_eq!(queue.pop(), Some(1));
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(3));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn test_array_queue_rounds_up_capacity() {
+        let queue: ArrayQueue<i32> = ArrayQueue::new(3);
+        assert_eq!(queue.capacity(), 4);
+    }
+
+    #[test]
+    fn test_array_queue_full_returns_err() {
+        let queue = ArrayQueue::new(2);
+        queue.push(1).unwrap();
+        queue.push(2).unwrap();
+        assert_eq!(queue.push(3), Err(3));
+
+        assert_eq!(queue.pop(), Some(1));
+        queue.push(3).unwrap();
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_pop_timeout_elapses_on_empty_queue() {
+        let queue: LockFreeQueue<i32> = LockFreeQueue::new();
+        assert_eq!(queue.pop_timeout(Duration::from_millis(10)), None);
+    }
+
+    #[test]
+    fn test_pop_blocking_wakes_on_push() {
+        let queue = Arc::new(LockFreeQueue::new());
+        let consumer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.pop_blocking())
+        };
+        thread::sleep(Duration::from_millis(20));
+        queue.push(42);
+        assert_eq!(consumer.join().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_pop_returns_none_while_only_reservations_are_queued() {
+        let queue = Arc::new(LockFreeQueue::new());
+        let consumer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.pop_blocking())
+        };
+        // Give the consumer time to park on a `Blocked` reservation node.
+        // A plain, non-blocking `pop()` must not mistake that reservation
+        // for data - it belongs to the other thread.
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(queue.pop(), None);
+
+        queue.push(5);
+        assert_eq!(consumer.join().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_pop_blocking_fans_out_to_multiple_waiting_consumers() {
+        let queue = Arc::new(LockFreeQueue::new());
+        let consumers: Vec<_> = (0..3)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || queue.pop_blocking())
+            })
+            .collect();
+        thread::sleep(Duration::from_millis(20));
+        queue.push(1);
+        queue.push(2);
+        queue.push(3);
+
+        let mut delivered: Vec<i32> = consumers
+            .into_iter()
+            .map(|c| c.join().unwrap())
+            .collect();
+        delivered.sort_unstable();
+        assert_eq!(delivered, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_push_after_fulfilled_reservation_behaves_like_ordinary_queue() {
+        let queue = Arc::new(LockFreeQueue::new());
+        let consumer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.pop_blocking())
+        };
+        thread::sleep(Duration::from_millis(20));
+        queue.push(1);
+        assert_eq!(consumer.join().unwrap(), 1);
+
+        // The reservation that was just fulfilled left its node behind as
+        // the new head sentinel. A later, unrelated push must still be
+        // able to land and be popped normally rather than spinning
+        // forever behind it.
+        queue.push(2);
+        assert_eq!(queue.pop(), Some(2));
+    }
+
+    #[test]
+    fn test_push_after_cancelled_reservation_behaves_like_ordinary_queue() {
+        let queue: LockFreeQueue<i32> = LockFreeQueue::new();
+        assert_eq!(queue.pop_timeout(Duration::from_millis(10)), None);
+
+        // Same as above, but the reservation resolved via cancellation
+        // instead of fulfillment.
+        queue.push(2);
+        assert_eq!(queue.pop(), Some(2));
+    }
+
+    #[test]
+    fn test_push_blocking_wakes_on_pop() {
+        let queue = Arc::new(ArrayQueue::new(2));
+        queue.push(1).unwrap();
+        queue.push(2).unwrap();
+        assert_eq!(queue.push(99), Err(99));
+
+        let producer = {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || queue.push_blocking(3))
+        };
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(queue.pop(), Some(1));
+        producer.join().unwrap();
+        assert_eq!(queue.pop(), Some(2));
+        assert_eq!(queue.pop(), Some(3));
+    }
+
+    #[test]
+    fn test_select_pop_prefers_ready_queue() {
+        let a = LockFreeQueue::new();
+        let b = LockFreeQueue::new();
+        b.push(7);
+
+        let selector = Selector::new().with_queue(&a).with_queue(&b);
+        assert_eq!(selector.select_pop(), (1, 7));
+    }
+
+    #[test]
+    fn test_select_pop_blocks_until_either_queue_is_pushed() {
+        let a = Arc::new(LockFreeQueue::new());
+        let b = Arc::new(LockFreeQueue::new());
+
+        let (a2, b2) = (Arc::clone(&a), Arc::clone(&b));
+        let waiter = thread::spawn(move || {
+            let selector = Selector::new().with_queue(&*a2).with_queue(&*b2);
+            selector.select_pop()
+        });
+        thread::sleep(Duration::from_millis(20));
+        b.push(9);
+
+        assert_eq!(waiter.join().unwrap(), (1, 9));
+    }
+
+    #[test]
+    fn test_spsc_push_pop() {
+        let (producer, consumer) = SpscQueue::split();
+        producer.push(1);
+        producer.push(2);
+        producer.push(3);
+
+        assert_eq!(consumer.pop(), Some(1));
+        assert_eq!(consumer.pop(), Some(2));
+        assert_eq!(consumer.pop(), Some(3));
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn test_spsc_recycles_nodes() {
+        let (producer, consumer) = SpscQueue::split();
+        for i in 0..100 {
+            producer.push(i);
+            assert_eq!(consumer.pop(), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_spsc_across_threads() {
+        let (producer, consumer) = SpscQueue::split();
+        let producer_thread = thread::spawn(move || {
+            for i in 0..1000 {
+                producer.push(i);
+            }
+        });
+        let consumer_thread = thread::spawn(move || {
+            let mut received = Vec::new();
+            while received.len() < 1000 {
+                if let Some(v) = consumer.pop() {
+                    received.push(v);
+                }
+            }
+            received
+        });
+        producer_thread.join().unwrap();
+        let received = consumer_thread.join().unwrap();
+        assert_eq!(received, (0..1000).collect::<Vec<_>>());
+    }
+
+    /// Manual throughput benchmark for the cache-padded `head`/`tail`: not
+    /// part of the default `cargo test` run (`#[ignore]`d), since it's
+    /// timing-sensitive and meant to be run deliberately, in release mode,
+    /// on a multi-core machine:
+    /// `cargo test --release -- --ignored bench_contended_throughput`.
+    #[test]
+    #[ignore]
+    fn bench_contended_throughput() {
+        use std::sync::Barrier;
+
+        const PRODUCERS: usize = 4;
+        const CONSUMERS: usize = 4;
+        const PER_PRODUCER: usize = 200_000;
+
+        let queue = Arc::new(LockFreeQueue::new());
+        let barrier = Arc::new(Barrier::new(PRODUCERS + CONSUMERS + 1));
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    for i in 0..PER_PRODUCER {
+                        queue.push(i);
+                    }
+                })
+            })
+            .collect();
+
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    let mut popped = 0usize;
+                    while popped < (PRODUCERS * PER_PRODUCER) / CONSUMERS {
+                        if queue.pop().is_some() {
+                            popped += 1;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        barrier.wait();
+        let start = Instant::now();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        for consumer in consumers {
+            consumer.join().unwrap();
+        }
+        let elapsed = start.elapsed();
+
+        let total_ops = PRODUCERS * PER_PRODUCER * 2;
+        eprintln!(
+            "{total_ops} push/pop ops in {elapsed:?} ({:.1} Mops/s)",
+            total_ops as f64 / elapsed.as_secs_f64() / 1e6
+        );
+    }
 }